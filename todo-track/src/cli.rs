@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -10,6 +10,18 @@ pub struct Cli {
     pub command: Command,
 }
 
+/// Output format shared by the `scan`, `list`, and `check` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable, colored terminal output (the default)
+    Text,
+    /// Compact JSON array of TODOs
+    Json,
+    /// SARIF 2.1.0 log, for CI systems that render inline annotations
+    Sarif,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Scan files for TODO comments and store a snapshot
@@ -17,6 +29,14 @@ pub enum Command {
         /// Path to scan (defaults to current directory)
         #[arg(default_value = ".")]
         path: PathBuf,
+
+        /// Scan everything, ignoring .gitignore and .todo-trackignore
+        #[arg(long, default_value_t = false)]
+        no_ignore: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// List TODOs from the most recent snapshot
@@ -32,6 +52,10 @@ pub enum Command {
         /// Run git blame to show authorship info
         #[arg(long, default_value_t = false)]
         blame: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// Show historical trend of TODO counts
@@ -42,5 +66,99 @@ pub enum Command {
         /// Maximum allowed TODO count
         #[arg(long)]
         max: usize,
+
+        /// Fail if any TODO's git blame date is older than this many days
+        #[arg(long)]
+        max_age_days: Option<i64>,
+
+        /// Fail if the summed age (in days) of all TODOs exceeds this budget
+        #[arg(long)]
+        max_total_age: Option<i64>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Show which TODOs were added, resolved, or moved between two snapshots
+    Diff {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Snapshot ID to diff from (defaults to the second-most-recent snapshot)
+        #[arg(long)]
+        from: Option<i64>,
+
+        /// Snapshot ID to diff to (defaults to the most recent snapshot)
+        #[arg(long)]
+        to: Option<i64>,
+    },
+
+    /// Rank TODOs by how long they've sat unresolved, using git blame dates
+    Age {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Only show TODOs with this keyword (e.g. FIXME)
+        #[arg(long)]
+        keyword: Option<String>,
+
+        /// Only show TODOs last touched by this git author
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Show only the N oldest TODOs
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+
+    /// Show a TODO with syntax-highlighted source context around it
+    Show {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// The TODO's id, as shown by 'list'
+        id: i64,
+    },
+
+    /// Verify issue_ref links against the forge and optionally file new issues
+    Sync {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// File a new forge issue for TODOs/FIXMEs lacking an issue_ref
+        #[arg(long, default_value_t = false)]
+        file_issues: bool,
+    },
+
+    /// Render the latest snapshot's TODOs into a standalone HTML report
+    Report {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// File to write the HTML report to
+        #[arg(long, default_value = "todo-report.html")]
+        out: PathBuf,
+    },
+
+    /// Commit and push the snapshot database to a git remote, so TODO
+    /// history travels alongside the code
+    Publish {
+        /// Path to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Remote to push to (defaults to "origin")
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Commit message (defaults to a generated "todo-track: snapshot #N, <count> TODOs")
+        #[arg(long)]
+        message: Option<String>,
     },
 }
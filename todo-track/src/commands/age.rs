@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use colored::Colorize;
+
+use crate::error::TodoTrackError;
+use crate::git;
+use crate::storage;
+
+/// Execute the `age` command: rank TODOs by how long they've sat
+/// unresolved, using git blame dates.
+pub fn run(
+    path: &Path,
+    keyword: Option<String>,
+    author: Option<String>,
+    limit: Option<usize>,
+) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    if !git::is_git_repo(&root) {
+        anyhow::bail!(
+            "{} is not a git repository; 'age' needs git blame to work",
+            root.display()
+        );
+    }
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let snapshot = storage::get_latest_snapshot(&conn)?.ok_or(TodoTrackError::NoSnapshots)?;
+    let mut todos = storage::get_todos_for_snapshot(&conn, snapshot.id)?;
+
+    println!("{}", "Running git blame (this may take a moment)...".dimmed());
+    git::populate_blame(&conn, &root, &mut todos)?;
+    println!();
+
+    if let Some(ref kw) = keyword {
+        let kw_upper = kw.to_uppercase();
+        todos.retain(|t| t.keyword == kw_upper);
+    }
+    if let Some(ref author) = author {
+        todos.retain(|t| t.git_author.as_deref() == Some(author.as_str()));
+    }
+
+    todos.sort_by(|a, b| {
+        let date_a = a.git_date.as_deref().unwrap_or("9999-99-99");
+        let date_b = b.git_date.as_deref().unwrap_or("9999-99-99");
+        date_a.cmp(date_b)
+    });
+
+    if let Some(n) = limit {
+        todos.truncate(n);
+    }
+
+    if todos.is_empty() {
+        println!("{}", "No TODOs found.".green());
+        return Ok(());
+    }
+
+    let today = Utc::now().date_naive();
+
+    println!(
+        "  {:<10} {:<12} {:<40} {}",
+        "Age".bold(),
+        "Keyword".bold(),
+        "Location".bold(),
+        "Description".bold()
+    );
+    println!("  {}", "-".repeat(78));
+
+    for todo in &todos {
+        let age_str = match todo
+            .git_date
+            .as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        {
+            Some(date) => format!("{} days", (today - date).num_days()),
+            None => "unknown".to_string(),
+        };
+
+        let location = format!("{}:{}", todo.file_path, todo.line_number);
+        let author = todo.git_author.as_deref().unwrap_or("?");
+
+        println!(
+            "  {:<10} {:<12} {:<40} {} {}",
+            age_str,
+            todo.keyword,
+            location,
+            todo.description,
+            format!("({})", author).dimmed()
+        );
+    }
+
+    Ok(())
+}
@@ -1,58 +1,152 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::process;
 
 use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
 use colored::Colorize;
 
+use crate::cli::OutputFormat;
+use crate::config::Config;
 use crate::error::TodoTrackError;
+use crate::git;
+use crate::output::{self, ReportableTodo};
 use crate::storage;
 
-/// Execute the `check` command: CI gate that fails if TODO count exceeds max.
-pub fn run(max: usize) -> Result<()> {
+/// Execute the `check` command: CI gate that fails if TODO count exceeds max,
+/// either in aggregate or for any individual keyword's configured budget, or
+/// if TODO age exceeds an `--max-age-days`/`--max-total-age` debt budget.
+pub fn run(
+    max: usize,
+    max_age_days: Option<i64>,
+    max_total_age: Option<i64>,
+    format: OutputFormat,
+) -> Result<()> {
     let root = Path::new(".")
         .canonicalize()
         .context("Failed to resolve current directory")?;
 
+    let config = Config::load(&root)?;
     let conn = storage::open_db(&root).context("Failed to open database")?;
 
-    let count = storage::get_latest_todo_count(&conn)?
-        .ok_or(TodoTrackError::NoSnapshots)?;
+    let snapshot = storage::get_latest_snapshot(&conn)?.ok_or(TodoTrackError::NoSnapshots)?;
+    let count_usize = snapshot.todo_count as usize;
+    let mut todos = storage::get_todos_for_snapshot(&conn, snapshot.id)?;
 
-    let count_usize = count as usize;
+    let mut per_keyword_counts: HashMap<String, usize> = HashMap::new();
+    for todo in &todos {
+        *per_keyword_counts.entry(todo.keyword.clone()).or_insert(0) += 1;
+    }
+
+    // The CLI --max flag always applies; config.check.max, if set, tightens
+    // (never loosens) the aggregate ceiling.
+    let aggregate_max = config.check.max.map(|m| m.min(max)).unwrap_or(max);
+    let mut failures: Vec<String> = Vec::new();
+
+    if count_usize > aggregate_max {
+        failures.push(format!(
+            "{} TODOs exceed maximum of {}",
+            count_usize, aggregate_max
+        ));
+    }
+
+    for (keyword, ceiling) in &config.check.per_keyword_max {
+        let actual = per_keyword_counts.get(keyword.as_str()).copied().unwrap_or(0);
+        if actual > *ceiling {
+            failures.push(format!(
+                "{} {} exceed per-keyword budget of {}",
+                actual, keyword, ceiling
+            ));
+        }
+    }
+
+    if max_age_days.is_some() || max_total_age.is_some() {
+        if git::is_git_repo(&root) {
+            git::populate_blame(&conn, &root, &mut todos)?;
+
+            let today = Utc::now().date_naive();
+            let ages: Vec<(i64, &storage::StoredTodo)> = todos
+                .iter()
+                .filter_map(|t| {
+                    let date = NaiveDate::parse_from_str(t.git_date.as_deref()?, "%Y-%m-%d").ok()?;
+                    Some(((today - date).num_days(), t))
+                })
+                .collect();
 
-    println!(
-        "TODO count: {} (max allowed: {})",
-        if count_usize > max {
-            count.to_string().red().bold()
+            if let Some(max_age) = max_age_days {
+                let mut oldest: Vec<&(i64, &storage::StoredTodo)> =
+                    ages.iter().filter(|(age, _)| *age > max_age).collect();
+                oldest.sort_by(|a, b| b.0.cmp(&a.0));
+
+                if !oldest.is_empty() {
+                    failures.push(format!(
+                        "{} TODOs are older than {} days (oldest: {})",
+                        oldest.len(),
+                        max_age,
+                        oldest
+                            .iter()
+                            .take(5)
+                            .map(|(age, t)| format!("{}:{} ({age}d)", t.file_path, t.line_number))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+
+            if let Some(max_total) = max_total_age {
+                let total_age: i64 = ages.iter().map(|(age, _)| age).sum();
+                if total_age > max_total {
+                    failures.push(format!(
+                        "total TODO age of {total_age} days exceeds debt budget of {max_total}"
+                    ));
+                }
+            }
         } else {
-            count.to_string().green().bold()
-        },
-        max
-    );
-
-    if count_usize > max {
-        println!(
-            "\n{}",
-            format!(
-                "FAIL: {} TODOs exceed maximum of {}. Reduce by {} to pass.",
-                count_usize,
-                max,
-                count_usize - max
-            )
-            .red()
-            .bold()
-        );
+            eprintln!(
+                "{}",
+                "Warning: not a git repository; skipping age-based checks.".yellow()
+            );
+        }
+    }
+
+    match format {
+        OutputFormat::Text => {
+            println!(
+                "TODO count: {} (max allowed: {})",
+                if failures.is_empty() {
+                    snapshot.todo_count.to_string().green().bold()
+                } else {
+                    snapshot.todo_count.to_string().red().bold()
+                },
+                aggregate_max
+            );
+
+            if failures.is_empty() {
+                println!(
+                    "\n{}",
+                    format!("PASS: {} TODOs within all configured budgets.", count_usize)
+                        .green()
+                        .bold()
+                );
+            } else {
+                for failure in &failures {
+                    println!("{}", format!("FAIL: {failure}.").red().bold());
+                }
+            }
+        }
+        OutputFormat::Json | OutputFormat::Sarif => {
+            let reportable: Vec<ReportableTodo> = todos.iter().map(ReportableTodo::from).collect();
+            let body = match format {
+                OutputFormat::Json => output::to_json(&reportable)?,
+                OutputFormat::Sarif => output::to_sarif(&reportable, &config)?,
+                OutputFormat::Text => unreachable!(),
+            };
+            println!("{}", body);
+        }
+    }
+
+    if !failures.is_empty() {
         process::exit(1);
-    } else {
-        println!(
-            "\n{}",
-            format!(
-                "PASS: {} TODOs within limit of {}.",
-                count_usize, max
-            )
-            .green()
-            .bold()
-        );
     }
 
     Ok(())
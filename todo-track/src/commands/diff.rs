@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::error::TodoTrackError;
+use crate::storage::{self, StoredTodo};
+
+/// Key used to match the "same" TODO across two snapshots, independent of
+/// exact line number (which can shift as surrounding code changes).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TodoKey {
+    file_path: String,
+    keyword: String,
+    description: String,
+}
+
+/// Normalize a description for matching: trim whitespace, lowercase, and
+/// strip trailing comment terminators (e.g. `*/`) that are an artifact of
+/// where a block comment happens to end rather than the TODO text itself.
+fn normalize_description(description: &str) -> String {
+    description
+        .trim()
+        .trim_end_matches("*/")
+        .trim()
+        .to_lowercase()
+}
+
+fn todo_key(todo: &StoredTodo) -> TodoKey {
+    TodoKey {
+        file_path: todo.file_path.clone(),
+        keyword: todo.keyword.clone(),
+        description: normalize_description(&todo.description),
+    }
+}
+
+fn group_by_key(todos: &[StoredTodo]) -> HashMap<TodoKey, Vec<&StoredTodo>> {
+    let mut groups: HashMap<TodoKey, Vec<&StoredTodo>> = HashMap::new();
+    for todo in todos {
+        groups.entry(todo_key(todo)).or_default().push(todo);
+    }
+    groups
+}
+
+/// Execute the `diff` command: classify TODOs as added, resolved, or moved
+/// between two snapshots.
+pub fn run(path: &Path, from: Option<i64>, to: Option<i64>) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+
+    let snapshots = storage::get_snapshots(&conn)?;
+    if snapshots.len() < 2 {
+        return Err(TodoTrackError::NotEnoughSnapshots(snapshots.len()).into());
+    }
+
+    let from_id = from.unwrap_or(snapshots[snapshots.len() - 2].id);
+    let to_id = to.unwrap_or(snapshots[snapshots.len() - 1].id);
+
+    let from_snapshot = storage::get_snapshot(&conn, from_id)?
+        .with_context(|| format!("No snapshot #{} found", from_id))?;
+    let to_snapshot = storage::get_snapshot(&conn, to_id)?
+        .with_context(|| format!("No snapshot #{} found", to_id))?;
+
+    let from_todos = storage::get_todos_for_snapshot(&conn, from_id)?;
+    let to_todos = storage::get_todos_for_snapshot(&conn, to_id)?;
+
+    println!(
+        "{}",
+        format!(
+            "Diffing snapshot #{} ({}) -> #{} ({})",
+            from_snapshot.id, from_snapshot.timestamp, to_snapshot.id, to_snapshot.timestamp
+        )
+        .dimmed()
+    );
+    println!();
+
+    let mut from_groups = group_by_key(&from_todos);
+    let to_groups = group_by_key(&to_todos);
+
+    let mut added: Vec<&StoredTodo> = Vec::new();
+    let mut resolved: Vec<&StoredTodo> = Vec::new();
+    let mut moved: Vec<(&StoredTodo, &StoredTodo)> = Vec::new();
+    let mut unchanged_count = 0usize;
+
+    for (key, to_list) in &to_groups {
+        let from_list = from_groups.remove(key).unwrap_or_default();
+        let matched = from_list.len().min(to_list.len());
+
+        for i in 0..matched {
+            let from_todo = from_list[i];
+            let to_todo = to_list[i];
+            if from_todo.line_number == to_todo.line_number {
+                unchanged_count += 1;
+            } else {
+                moved.push((from_todo, to_todo));
+            }
+        }
+
+        added.extend(to_list[matched..].iter().copied());
+        resolved.extend(from_list[matched..].iter().copied());
+    }
+
+    // Anything left in from_groups (after removing matched keys) never
+    // reappeared in `to` and is resolved, alongside the surplus `from`
+    // entries of shared keys collected above.
+    resolved.extend(from_groups.into_values().flatten());
+
+    // `from_groups`/`to_groups` are HashMaps, so the order these were
+    // accumulated in is randomized per run. Sort by `(file_path,
+    // line_number)` before printing, same as `scan`, so output is stable
+    // across invocations of the same snapshot data.
+    added.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+    resolved.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+    moved.sort_by(|a, b| {
+        (&a.0.file_path, a.0.line_number).cmp(&(&b.0.file_path, b.0.line_number))
+    });
+
+    if !added.is_empty() {
+        println!("{}", format!("Added ({})", added.len()).green().bold());
+        for todo in &added {
+            println!(
+                "  {} {} {}",
+                format!("{}:{}", todo.file_path, todo.line_number).bold(),
+                todo.keyword.yellow(),
+                todo.description
+            );
+        }
+        println!();
+    }
+
+    if !resolved.is_empty() {
+        println!(
+            "{}",
+            format!("Resolved ({})", resolved.len()).red().bold()
+        );
+        for todo in &resolved {
+            println!(
+                "  {} {} {}",
+                format!("{}:{}", todo.file_path, todo.line_number).bold(),
+                todo.keyword.yellow(),
+                todo.description
+            );
+        }
+        println!();
+    }
+
+    if !moved.is_empty() {
+        println!("{}", format!("Moved ({})", moved.len()).cyan().bold());
+        for (from_todo, to_todo) in &moved {
+            let delta = to_todo.line_number - from_todo.line_number;
+            println!(
+                "  {} {} {} {}",
+                format!("{}:{} -> :{}", from_todo.file_path, from_todo.line_number, to_todo.line_number).bold(),
+                to_todo.keyword.yellow(),
+                to_todo.description,
+                format!("({:+})", delta).dimmed()
+            );
+        }
+        println!();
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} added, {} resolved, {} moved, {} unchanged",
+            added.len(),
+            resolved.len(),
+            moved.len(),
+            unchanged_count
+        )
+        .dimmed()
+    );
+
+    Ok(())
+}
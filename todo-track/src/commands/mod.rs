@@ -0,0 +1,10 @@
+pub mod age;
+pub mod check;
+pub mod diff;
+pub mod list;
+pub mod publish;
+pub mod report;
+pub mod scan;
+pub mod show;
+pub mod sync;
+pub mod trend;
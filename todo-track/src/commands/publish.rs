@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::error::TodoTrackError;
+use crate::git;
+use crate::storage;
+
+/// Execute the `publish` command: commit and push the snapshot database
+/// (`.todo-track/db.sqlite`) to a git remote.
+pub fn run(path: &Path, remote: Option<String>, message: Option<String>) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    if !git::is_git_repo(&root) {
+        anyhow::bail!("{} is not a git repository.", root.display());
+    }
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let snapshot = storage::get_latest_snapshot(&conn)?.ok_or(TodoTrackError::NoSnapshots)?;
+
+    let db_path = storage::db_path(&root).context("Failed to resolve database path")?;
+
+    git::commit_and_push(
+        &root,
+        &[db_path.as_path()],
+        remote.as_deref(),
+        message,
+        snapshot.id,
+        snapshot.todo_count,
+    )?;
+
+    println!(
+        "{}",
+        format!(
+            "Pushed snapshot #{} ({} TODOs) to {}.",
+            snapshot.id,
+            snapshot.todo_count,
+            remote.as_deref().unwrap_or("origin")
+        )
+        .green()
+    );
+
+    Ok(())
+}
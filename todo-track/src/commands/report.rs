@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use comrak::plugins::syntect::SyntectAdapter;
+use comrak::{markdown_to_html_with_plugins, ComrakOptions, ComrakPlugins};
+
+use crate::error::TodoTrackError;
+use crate::git;
+use crate::storage::{self, StoredTodo};
+
+/// Lines of source to quote above and below each TODO in the report.
+const CONTEXT_LINES: usize = 2;
+
+/// Execute the `report` command: render the latest snapshot's TODOs into a
+/// standalone HTML page, grouped by file then keyword, with syntax-highlighted
+/// context snippets and blame info when available.
+pub fn run(path: &Path, out: &Path) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let snapshot = storage::get_latest_snapshot(&conn)?.ok_or(TodoTrackError::NoSnapshots)?;
+    let mut todos = storage::get_todos_for_snapshot(&conn, snapshot.id)?;
+
+    if git::is_git_repo(&root) {
+        git::populate_blame(&conn, &root, &mut todos)?;
+    }
+
+    // Oldest-first within each file and keyword, reusing the same date-ascending sort
+    // `list --oldest` uses so the report reads worst-debt-first.
+    todos.sort_by(|a, b| {
+        let date_a = a.git_date.as_deref().unwrap_or("9999-99-99");
+        let date_b = b.git_date.as_deref().unwrap_or("9999-99-99");
+        date_a.cmp(date_b)
+    });
+
+    let markdown = build_markdown(&root, &todos);
+
+    let adapter = SyntectAdapter::new(Some("base16-ocean.dark"));
+    let options = ComrakOptions::default();
+    let mut plugins = ComrakPlugins::default();
+    plugins.render.codefence_syntax_highlighter = Some(&adapter);
+
+    let body = markdown_to_html_with_plugins(&markdown, &options, &plugins);
+    let html = wrap_html(&body, snapshot.id, snapshot.todo_count);
+
+    fs::write(out, html).with_context(|| format!("Failed to write {}", out.display()))?;
+
+    println!(
+        "{}",
+        format!("Report for snapshot #{} written to {}.", snapshot.id, out.display()).green()
+    );
+
+    Ok(())
+}
+
+/// Group TODOs by file, preserving the oldest-first order within each file.
+fn group_by_file(todos: &[StoredTodo]) -> BTreeMap<&str, Vec<&StoredTodo>> {
+    let mut by_file: BTreeMap<&str, Vec<&StoredTodo>> = BTreeMap::new();
+    for todo in todos {
+        by_file.entry(todo.file_path.as_str()).or_default().push(todo);
+    }
+    by_file
+}
+
+fn build_markdown(root: &Path, todos: &[StoredTodo]) -> String {
+    let mut md = String::new();
+    md.push_str("# TODO Report\n\n");
+
+    for (file_path, file_todos) in group_by_file(todos) {
+        md.push_str(&format!("## {file_path}\n\n"));
+
+        let extension = Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+        let source_lines = fs::read_to_string(root.join(file_path))
+            .ok()
+            .map(|c| c.lines().map(str::to_string).collect::<Vec<_>>());
+
+        // Stable sort, so the oldest-first order survives within each keyword.
+        let mut file_todos = file_todos;
+        file_todos.sort_by(|a, b| a.keyword.cmp(&b.keyword));
+        let mut current_keyword: Option<&str> = None;
+
+        for todo in file_todos {
+            if current_keyword != Some(todo.keyword.as_str()) {
+                md.push_str(&format!("### {}\n\n", todo.keyword));
+                current_keyword = Some(todo.keyword.as_str());
+            }
+
+            md.push_str(&format!(
+                "**{}** line {}: {}\n\n",
+                todo.keyword, todo.line_number, todo.description
+            ));
+
+            if let (Some(author), Some(date)) = (&todo.git_author, &todo.git_date) {
+                md.push_str(&format!("_{author} on {date}_\n\n"));
+            }
+
+            if let Some(ref lines) = source_lines {
+                let target = todo.line_number as usize;
+                let start = target.saturating_sub(CONTEXT_LINES).max(1);
+                let end = (target + CONTEXT_LINES).min(lines.len());
+
+                md.push_str(&format!("```{extension}\n"));
+                for (idx, line) in lines.iter().enumerate() {
+                    let line_number = idx + 1;
+                    if line_number >= start && line_number <= end {
+                        md.push_str(line);
+                        md.push('\n');
+                    }
+                }
+                md.push_str("```\n\n");
+            }
+        }
+    }
+
+    md
+}
+
+fn wrap_html(body: &str, snapshot_id: i64, todo_count: i64) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>TODO Report - snapshot #{snapshot_id}</title>\n<style>\nbody {{ font-family: sans-serif; max-width: 900px; margin: 2rem auto; padding: 0 1rem; }}\npre {{ padding: 0.75rem; overflow-x: auto; border-radius: 4px; }}\n</style>\n</head>\n<body>\n<p>Snapshot #{snapshot_id} &middot; {todo_count} TODOs</p>\n{body}\n</body>\n</html>\n"
+    )
+}
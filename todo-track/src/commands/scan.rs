@@ -4,29 +4,53 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use colored::Colorize;
 
+use crate::cli::OutputFormat;
+use crate::config::Config;
+use crate::output::{self, ReportableTodo};
 use crate::scanner;
 use crate::storage;
 
 /// Execute the `scan` command: scan for TODOs, print them, store snapshot.
-pub fn run(path: &Path) -> Result<()> {
+pub fn run(path: &Path, no_ignore: bool, format: OutputFormat) -> Result<()> {
     let root = path
         .canonicalize()
         .with_context(|| format!("Invalid scan path: {}", path.display()))?;
 
-    println!(
-        "{}",
-        format!("Scanning {}...", root.display()).dimmed()
-    );
+    if format == OutputFormat::Text {
+        println!("{}", format!("Scanning {}...", root.display()).dimmed());
+    }
 
-    let result = scanner::scan_directory(&root);
+    let config = Config::load(&root)?;
+    let result = scanner::scan_directory(&root, no_ignore, &config);
     let todo_count = result.todos.len();
 
+    match format {
+        OutputFormat::Text => print_text(&result, todo_count, &config),
+        OutputFormat::Json => {
+            let reportable: Vec<ReportableTodo> = result.todos.iter().map(ReportableTodo::from).collect();
+            println!("{}", output::to_json(&reportable)?);
+        }
+        OutputFormat::Sarif => {
+            let reportable: Vec<ReportableTodo> = result.todos.iter().map(ReportableTodo::from).collect();
+            println!("{}", output::to_sarif(&reportable, &config)?);
+        }
+    }
+
+    // Save snapshot to SQLite
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let snapshot_id =
+        storage::save_snapshot(&conn, &result.todos).context("Failed to save snapshot")?;
+
+    if format == OutputFormat::Text {
+        println!("{}", format!("Snapshot #{} saved.", snapshot_id).green());
+    }
+
+    Ok(())
+}
+
+fn print_text(result: &scanner::ScanResult, todo_count: usize, config: &Config) {
     // Count unique files with TODOs
-    let files_with_todos: HashSet<_> = result
-        .todos
-        .iter()
-        .map(|t| t.file_path.clone())
-        .collect();
+    let files_with_todos: HashSet<_> = result.todos.iter().map(|t| t.file_path.clone()).collect();
     let file_count = files_with_todos.len();
 
     // Print each TODO
@@ -38,13 +62,7 @@ pub fn run(path: &Path) -> Result<()> {
         )
         .bold();
 
-        let keyword = match todo.item.keyword.as_str() {
-            "TODO" => todo.item.keyword.yellow(),
-            "FIXME" => todo.item.keyword.red(),
-            "HACK" => todo.item.keyword.magenta(),
-            "XXX" => todo.item.keyword.red().bold(),
-            _ => todo.item.keyword.normal(),
-        };
+        let keyword = todo.item.keyword.color(config.color_for(&todo.item.keyword));
 
         let mut extras = Vec::new();
         if let Some(ref author) = todo.item.author {
@@ -67,10 +85,7 @@ pub fn run(path: &Path) -> Result<()> {
     }
 
     // Summary line with color based on count
-    let summary = format!(
-        "Found {} TODOs across {} files",
-        todo_count, file_count
-    );
+    let summary = format!("Found {} TODOs across {} files", todo_count, file_count);
     let colored_summary = if todo_count == 0 {
         summary.green()
     } else if todo_count <= 10 {
@@ -82,21 +97,9 @@ pub fn run(path: &Path) -> Result<()> {
     println!(
         "{}",
         format!(
-            "({} files scanned, {} skipped)",
-            result.files_scanned, result.files_skipped
+            "({} files scanned, {} skipped, {} ignored)",
+            result.files_scanned, result.files_skipped, result.files_ignored
         )
         .dimmed()
     );
-
-    // Save snapshot to SQLite
-    let conn = storage::open_db(&root).context("Failed to open database")?;
-    let snapshot_id =
-        storage::save_snapshot(&conn, &result.todos).context("Failed to save snapshot")?;
-
-    println!(
-        "{}",
-        format!("Snapshot #{} saved.", snapshot_id).green()
-    );
-
-    Ok(())
 }
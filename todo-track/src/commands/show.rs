@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
+use crate::error::TodoTrackError;
+use crate::storage;
+
+/// Lines of source to show above and below the TODO line.
+const CONTEXT_LINES: usize = 4;
+
+/// Execute the `show` command: print a TODO with syntax-highlighted source
+/// context around it, like a focused `git blame` view.
+pub fn run(path: &Path, id: i64) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let todo = storage::get_todo_by_id(&conn, id)?.ok_or(TodoTrackError::TodoNotFound(id))?;
+
+    let file_path = root.join(&todo.file_path);
+    let content = fs::read_to_string(&file_path)
+        .with_context(|| format!("Failed to read {}", file_path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let target_line = todo.line_number as usize;
+    let start = target_line.saturating_sub(CONTEXT_LINES).max(1);
+    let end = (target_line + CONTEXT_LINES).min(lines.len());
+
+    let keyword = match todo.keyword.as_str() {
+        "TODO" => todo.keyword.yellow(),
+        "FIXME" => todo.keyword.red(),
+        "HACK" => todo.keyword.magenta(),
+        "XXX" => todo.keyword.red().bold(),
+        _ => todo.keyword.normal(),
+    };
+
+    println!(
+        "{}",
+        format!("{}:{}", todo.file_path, todo.line_number).bold()
+    );
+    println!("  {} {}", keyword, todo.description);
+    println!();
+
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme_set = ThemeSet::load_defaults();
+    let theme = &theme_set.themes["base16-ocean.dark"];
+
+    let syntax = syntax_set
+        .find_syntax_for_file(&file_path)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    for (idx, line) in lines.iter().enumerate() {
+        let line_number = idx + 1;
+
+        // Always feed the line through the highlighter, even outside the
+        // printed window, so syntect's incremental parse state (open block
+        // comments, nested scopes, ...) is correct by the time we reach
+        // `start` instead of restarting as if `start` were line 1.
+        let ranges = highlighter
+            .highlight_line(line, &syntax_set)
+            .unwrap_or_default();
+
+        if line_number < start || line_number > end {
+            continue;
+        }
+
+        let escaped = as_24_bit_terminal_escaped(&ranges, false);
+
+        let gutter = if line_number == target_line {
+            ">".red().bold().to_string()
+        } else {
+            " ".to_string()
+        };
+
+        println!("{} {:>5} | {}\x1b[0m", gutter, line_number, escaped);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::error::TodoTrackError;
+use crate::forge::{self, ForgeConfig, IssueState};
+use crate::storage;
+
+/// Execute the `sync` command: verify each TODO's `issue_ref` against the
+/// forge's issue tracker, and optionally file new issues for bare TODOs.
+pub fn run(path: &Path, file_issues: bool) -> Result<()> {
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Invalid path: {}", path.display()))?;
+
+    let conn = storage::open_db(&root).context("Failed to open database")?;
+    let snapshot = storage::get_latest_snapshot(&conn)?.ok_or(TodoTrackError::NoSnapshots)?;
+    let todos = storage::get_todos_for_snapshot(&conn, snapshot.id)?;
+
+    let config = ForgeConfig::discover(&root).context("Failed to resolve forge configuration")?;
+
+    let mut flagged = 0;
+    let mut filed = 0;
+
+    for todo in &todos {
+        match &todo.issue_ref {
+            Some(issue_ref) => {
+                let Some(number) = forge::extract_issue_number(issue_ref) else {
+                    continue;
+                };
+                match config.check_issue(number) {
+                    Ok(IssueState::Open) => {}
+                    Ok(IssueState::Closed) => {
+                        flagged += 1;
+                        println!(
+                            "  {} {}:{} references closed issue #{}",
+                            "WARN".yellow().bold(),
+                            todo.file_path,
+                            todo.line_number,
+                            number
+                        );
+                    }
+                    Ok(IssueState::Missing) => {
+                        flagged += 1;
+                        println!(
+                            "  {} {}:{} references nonexistent issue #{}",
+                            "WARN".red().bold(),
+                            todo.file_path,
+                            todo.line_number,
+                            number
+                        );
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "{}",
+                            format!("Warning: failed to check #{}: {}", number, e).dimmed()
+                        );
+                    }
+                }
+            }
+            None if file_issues && matches!(todo.keyword.as_str(), "TODO" | "FIXME") => {
+                let title = format!("{}: {}", todo.keyword, todo.description);
+                let body = format!("Found at `{}:{}`.", todo.file_path, todo.line_number);
+                match config.create_issue(&title, &body) {
+                    Ok(number) => {
+                        let issue_ref = format!("#{}", number);
+                        storage::update_issue_ref(&conn, todo.id, &issue_ref)?;
+                        filed += 1;
+                        println!(
+                            "  {} filed issue #{} for {}:{}",
+                            "NEW".green().bold(),
+                            number,
+                            todo.file_path,
+                            todo.line_number
+                        );
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "{}",
+                            format!(
+                                "Warning: failed to file issue for {}:{}: {}",
+                                todo.file_path, todo.line_number, e
+                            )
+                            .dimmed()
+                        );
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!("{} flagged, {} issues filed", flagged, filed).dimmed()
+    );
+
+    Ok(())
+}
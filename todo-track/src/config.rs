@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::TodoTrackError;
+
+/// Name of the config file looked up at the scanned root, à la clippy's
+/// `clippy.toml` / cargo's `Cargo.toml` convention.
+const CONFIG_FILE: &str = "todo-track.toml";
+
+/// A single `[[keywords]]` entry: the tag text to match, its display color,
+/// and an optional severity weight used when reporting via SARIF.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeywordConfig {
+    pub tag: String,
+    #[serde(default = "default_color")]
+    pub color: String,
+    #[serde(default)]
+    pub severity: Option<String>,
+}
+
+fn default_color() -> String {
+    "normal".to_string()
+}
+
+/// `[scan]` section: glob include/exclude patterns and the max file size to read.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub max_file_size: u64,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_file_size: 1_048_576,
+        }
+    }
+}
+
+/// `[check]` section: an overall aggregate ceiling plus per-keyword ceilings,
+/// e.g. `FIXME = 0` alongside a named `max = 50`.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CheckConfig {
+    pub max: Option<usize>,
+    #[serde(flatten)]
+    pub per_keyword_max: HashMap<String, usize>,
+}
+
+/// Top-level `todo-track.toml` shape.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub keywords: Vec<KeywordConfig>,
+    #[serde(default)]
+    pub scan: ScanConfig,
+    #[serde(default)]
+    pub check: CheckConfig,
+}
+
+impl Config {
+    /// Load `todo-track.toml` from the scanned root, falling back to the
+    /// built-in TODO/FIXME/HACK/XXX defaults when no file is present.
+    pub fn load(root: &Path) -> Result<Config, TodoTrackError> {
+        let path = root.join(CONFIG_FILE);
+
+        if !path.exists() {
+            return Ok(Config::default_keywords());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        let mut config: Config =
+            toml::from_str(&contents).map_err(|e| TodoTrackError::Config(e.to_string()))?;
+
+        if config.keywords.is_empty() {
+            config.keywords = Config::default_keywords().keywords;
+        }
+
+        Ok(config)
+    }
+
+    pub(crate) fn default_keywords() -> Config {
+        Config {
+            keywords: vec![
+                KeywordConfig { tag: "TODO".to_string(), color: "yellow".to_string(), severity: None },
+                KeywordConfig { tag: "FIXME".to_string(), color: "red".to_string(), severity: None },
+                KeywordConfig { tag: "HACK".to_string(), color: "magenta".to_string(), severity: None },
+                KeywordConfig { tag: "XXX".to_string(), color: "red".to_string(), severity: None },
+            ],
+            scan: ScanConfig::default(),
+            check: CheckConfig::default(),
+        }
+    }
+
+    /// All configured keyword tags, for building the scan regex.
+    pub fn keyword_tags(&self) -> Vec<String> {
+        self.keywords.iter().map(|k| k.tag.clone()).collect()
+    }
+
+    /// Display color for a keyword, falling back to "normal" if unconfigured.
+    pub fn color_for(&self, keyword: &str) -> &str {
+        self.keywords
+            .iter()
+            .find(|k| k.tag.eq_ignore_ascii_case(keyword))
+            .map(|k| k.color.as_str())
+            .unwrap_or("normal")
+    }
+}
@@ -17,6 +17,21 @@ pub enum TodoTrackError {
     #[error("No snapshots found. Run 'todo-track scan' first.")]
     NoSnapshots,
 
+    #[error("Need at least two snapshots to diff, found {0}. Run 'todo-track scan' again.")]
+    NotEnoughSnapshots(usize),
+
     #[error("Git blame failed for {file}: {reason}")]
     GitBlame { file: String, reason: String },
+
+    #[error("Forge API error: {0}")]
+    Forge(String),
+
+    #[error("No TODO found with id {0}")]
+    TodoNotFound(i64),
+
+    #[error("Invalid todo-track.toml: {0}")]
+    Config(String),
+
+    #[error("Git sync failed: {0}")]
+    GitSync(String),
 }
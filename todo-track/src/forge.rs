@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::TodoTrackError;
+
+/// Where to reach the forge's REST API and how to authenticate against it.
+#[derive(Debug, Clone)]
+pub struct ForgeConfig {
+    pub server_url: String,
+    pub repo_slug: String,
+    pub token: Option<String>,
+}
+
+/// The result of looking up an `issue_ref` against the forge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueState {
+    Open,
+    Closed,
+    Missing,
+}
+
+#[derive(Deserialize)]
+struct IssueResponse {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct CreateIssueResponse {
+    number: u64,
+}
+
+impl ForgeConfig {
+    /// Resolve forge configuration the way CI already publishes it
+    /// (`GITHUB_REPOSITORY`, `GITHUB_SERVER_URL`, `REPO_TOKEN`), falling back
+    /// to parsing the `origin` remote from `.git/config` for local runs.
+    pub fn discover(repo_root: &Path) -> Result<ForgeConfig, TodoTrackError> {
+        let token = std::env::var("REPO_TOKEN").ok();
+
+        if let (Ok(repo_slug), Ok(server_url)) = (
+            std::env::var("GITHUB_REPOSITORY"),
+            std::env::var("GITHUB_SERVER_URL"),
+        ) {
+            return Ok(ForgeConfig {
+                server_url,
+                repo_slug,
+                token,
+            });
+        }
+
+        let (server_url, repo_slug) = parse_origin_url(repo_root)?;
+        Ok(ForgeConfig {
+            server_url,
+            repo_slug,
+            token,
+        })
+    }
+
+    /// Base URL for REST calls: GitHub's API lives on a separate host,
+    /// while Forgejo/Gitea instances serve their API under `/api/v1` on the
+    /// same host as the web UI.
+    fn api_base(&self) -> String {
+        if self.server_url.contains("github.com") {
+            "https://api.github.com".to_string()
+        } else {
+            format!("{}/api/v1", self.server_url.trim_end_matches('/'))
+        }
+    }
+
+    fn authed(&self, req: ureq::Request) -> ureq::Request {
+        match &self.token {
+            Some(token) => req.set("Authorization", &format!("Bearer {}", token)),
+            None => req,
+        }
+    }
+
+    /// Look up whether an issue is open, closed, or doesn't exist.
+    pub fn check_issue(&self, number: u64) -> Result<IssueState, TodoTrackError> {
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            self.api_base(),
+            self.repo_slug,
+            number
+        );
+
+        match self.authed(ureq::get(&url)).call() {
+            Ok(resp) => {
+                let issue: IssueResponse = resp
+                    .into_json()
+                    .map_err(|e| TodoTrackError::Forge(e.to_string()))?;
+                Ok(if issue.state == "closed" {
+                    IssueState::Closed
+                } else {
+                    IssueState::Open
+                })
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(IssueState::Missing),
+            Err(e) => Err(TodoTrackError::Forge(format!(
+                "checking issue #{}: {}",
+                number, e
+            ))),
+        }
+    }
+
+    /// File a new issue and return its number.
+    pub fn create_issue(&self, title: &str, body: &str) -> Result<u64, TodoTrackError> {
+        let url = format!("{}/repos/{}/issues", self.api_base(), self.repo_slug);
+
+        let resp = self
+            .authed(ureq::post(&url))
+            .send_json(ureq::json!({ "title": title, "body": body }))
+            .map_err(|e| TodoTrackError::Forge(format!("creating issue: {}", e)))?;
+
+        let created: CreateIssueResponse = resp
+            .into_json()
+            .map_err(|e| TodoTrackError::Forge(e.to_string()))?;
+        Ok(created.number)
+    }
+}
+
+/// Extract a bare issue number from `#123`, `(#123)`, or `(123)` forms.
+pub fn extract_issue_number(issue_ref: &str) -> Option<u64> {
+    issue_ref
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim_start_matches('#')
+        .parse()
+        .ok()
+}
+
+/// Parse the `origin` remote out of a repo's `.git/config`.
+fn parse_origin_url(repo_root: &Path) -> Result<(String, String), TodoTrackError> {
+    let config_path = repo_root.join(".git").join("config");
+    let contents = fs::read_to_string(&config_path)
+        .map_err(|e| TodoTrackError::Forge(format!("reading {}: {}", config_path.display(), e)))?;
+
+    let mut in_origin = false;
+    let mut url = None;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_origin = trimmed == "[remote \"origin\"]";
+            continue;
+        }
+        if in_origin {
+            if let Some(rest) = trimmed.strip_prefix("url = ") {
+                url = Some(rest.trim().to_string());
+                break;
+            }
+        }
+    }
+
+    let url = url.ok_or_else(|| TodoTrackError::Forge("no 'origin' remote configured".to_string()))?;
+    parse_remote_url(&url)
+}
+
+/// Parse a git remote URL (`https://github.com/owner/repo.git` or
+/// `git@github.com:owner/repo.git`) into a (server_url, "owner/repo") pair.
+fn parse_remote_url(url: &str) -> Result<(String, String), TodoTrackError> {
+    let url = url.trim_end_matches(".git");
+
+    if let Some(rest) = url.strip_prefix("git@") {
+        let (host, path) = rest
+            .split_once(':')
+            .ok_or_else(|| TodoTrackError::Forge(format!("unrecognized remote URL: {}", url)))?;
+        return Ok((format!("https://{}", host), path.to_string()));
+    }
+
+    for scheme in ["https://", "http://"] {
+        if let Some(rest) = url.strip_prefix(scheme) {
+            let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+            return Ok((format!("{}{}", scheme, host), path.to_string()));
+        }
+    }
+
+    Err(TodoTrackError::Forge(format!(
+        "unrecognized remote URL: {}",
+        url
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_issue_number_hash() {
+        assert_eq!(extract_issue_number("#123"), Some(123));
+    }
+
+    #[test]
+    fn test_extract_issue_number_paren_hash() {
+        assert_eq!(extract_issue_number("(#123)"), Some(123));
+    }
+
+    #[test]
+    fn test_extract_issue_number_bare_paren() {
+        assert_eq!(extract_issue_number("(123)"), Some(123));
+    }
+
+    #[test]
+    fn test_parse_remote_url_https() {
+        let (server, slug) = parse_remote_url("https://github.com/acme/widgets.git").unwrap();
+        assert_eq!(server, "https://github.com");
+        assert_eq!(slug, "acme/widgets");
+    }
+
+    #[test]
+    fn test_parse_remote_url_ssh() {
+        let (server, slug) = parse_remote_url("git@github.com:acme/widgets.git").unwrap();
+        assert_eq!(server, "https://github.com");
+        assert_eq!(slug, "acme/widgets");
+    }
+}
@@ -1,60 +1,55 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::process::Command;
 
+use git2::{BlameOptions, Cred, Index, IndexEntry, IndexTime, PushOptions, RemoteCallbacks, Repository};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Default remote used by `commit_and_push` when none is specified.
+const DEFAULT_REMOTE: &str = "origin";
+
 use crate::error::TodoTrackError;
+use crate::storage::{self, StoredTodo};
 
 /// Result of a git blame for a specific line.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlameInfo {
     pub author: String,
     pub date: String,
 }
 
-/// Run `git blame` on a specific file and line to get author and date info.
-/// Uses the porcelain format for reliable parsing.
+fn blame_error(file_path: &str, e: git2::Error) -> TodoTrackError {
+    TodoTrackError::GitBlame {
+        file: file_path.to_string(),
+        reason: e.message().to_string(),
+    }
+}
+
+/// Blame a specific line of a file, opening the repository just for this
+/// call. For blaming many lines across a run, open a `Repository` once and
+/// use `blame_file_lines` directly instead.
 pub fn blame_line(
     repo_root: &Path,
     file_path: &str,
     line_number: usize,
 ) -> Result<BlameInfo, TodoTrackError> {
-    let line_spec = format!("{},{}", line_number, line_number);
-
-    let output = Command::new("git")
-        .args([
-            "blame",
-            "--porcelain",
-            "-L",
-            &line_spec,
-            "--",
-            file_path,
-        ])
-        .current_dir(repo_root)
-        .output()
-        .map_err(|e| TodoTrackError::GitBlame {
-            file: file_path.to_string(),
-            reason: format!("failed to execute git blame: {}", e),
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(TodoTrackError::GitBlame {
-            file: file_path.to_string(),
-            reason: stderr.to_string(),
-        });
-    }
-
-    parse_porcelain_blame(&String::from_utf8_lossy(&output.stdout))
+    let repo = Repository::discover(repo_root).map_err(|e| blame_error(file_path, e))?;
+    let results = blame_file_lines(&repo, file_path, &[line_number])?;
+    results
+        .into_iter()
+        .next()
+        .map(|(_, info)| info)
         .ok_or_else(|| TodoTrackError::GitBlame {
             file: file_path.to_string(),
-            reason: "failed to parse blame output".to_string(),
+            reason: format!("no blame hunk for line {}", line_number),
         })
 }
 
-/// Blame an entire file once and return BlameInfo for all requested lines.
-/// Much faster than calling blame_line N times (1 process spawn vs N).
+/// Blame an entire file once and return BlameInfo for all requested lines,
+/// using libgit2 directly rather than shelling out to `git blame`.
 pub fn blame_file_lines(
-    repo_root: &Path,
+    repo: &Repository,
     file_path: &str,
     line_numbers: &[usize],
 ) -> Result<HashMap<usize, BlameInfo>, TodoTrackError> {
@@ -62,92 +57,140 @@ pub fn blame_file_lines(
         return Ok(HashMap::new());
     }
 
-    let output = Command::new("git")
-        .args(["blame", "--porcelain", "--", file_path])
-        .current_dir(repo_root)
-        .output()
-        .map_err(|e| TodoTrackError::GitBlame {
-            file: file_path.to_string(),
-            reason: format!("failed to execute git blame: {}", e),
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(TodoTrackError::GitBlame {
-            file: file_path.to_string(),
-            reason: stderr.to_string(),
-        });
-    }
+    let mut opts = BlameOptions::new();
+    let blame = repo
+        .blame_file(Path::new(file_path), Some(&mut opts))
+        .map_err(|e| blame_error(file_path, e))?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let wanted: std::collections::HashSet<usize> = line_numbers.iter().copied().collect();
+    let wanted: HashSet<usize> = line_numbers.iter().copied().collect();
     let mut results = HashMap::new();
 
-    // Parse porcelain output: each block starts with a commit hash line
-    // containing the original line number and current line number.
-    let mut current_line_num: Option<usize> = None;
-    let mut current_author = String::from("Unknown");
-    let mut current_date = String::from("Unknown");
-
-    for line in stdout.lines() {
-        // Commit line: <hash> <orig-line> <final-line> [<num-lines>]
-        if line.len() >= 40 && line.chars().take(40).all(|c| c.is_ascii_hexdigit()) {
-            // Save previous block if it was a wanted line
-            if let Some(ln) = current_line_num {
-                if wanted.contains(&ln) {
-                    results.insert(ln, BlameInfo {
-                        author: current_author.clone(),
-                        date: current_date.clone(),
-                    });
-                }
-            }
-            // Parse the final line number (3rd field)
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            current_line_num = parts.get(2).and_then(|s| s.parse().ok());
-            current_author = String::from("Unknown");
-            current_date = String::from("Unknown");
-        } else if let Some(val) = line.strip_prefix("author ") {
-            current_author = val.trim().to_string();
-        } else if let Some(val) = line.strip_prefix("author-time ") {
-            if let Ok(ts) = val.trim().parse::<i64>() {
-                if let Some(dt) = chrono::DateTime::from_timestamp(ts, 0) {
-                    current_date = dt.format("%Y-%m-%d").to_string();
-                }
-            }
-        }
-    }
+    for &line_number in &wanted {
+        // git2 line numbers passed to `get_line` are 1-indexed, matching
+        // TodoItem::line_number, so no conversion is needed.
+        let Some(hunk) = blame.get_line(line_number) else {
+            continue;
+        };
 
-    // Don't forget the last block
-    if let Some(ln) = current_line_num {
-        if wanted.contains(&ln) {
-            results.insert(ln, BlameInfo {
-                author: current_author,
-                date: current_date,
-            });
-        }
+        let (author, date) = if hunk.final_commit_id().is_zero() {
+            // Uncommitted local edits have no real commit to attribute to.
+            ("Not Committed Yet".to_string(), "Uncommitted".to_string())
+        } else {
+            let sig = hunk.final_signature();
+            let author = sig.name().unwrap_or("Unknown").to_string();
+            let date = chrono::DateTime::from_timestamp(sig.when().seconds(), 0)
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            (author, date)
+        };
+
+        results.insert(line_number, BlameInfo { author, date });
     }
 
     Ok(results)
 }
 
-/// Parse a single-block porcelain blame output into BlameInfo.
-fn parse_porcelain_blame(stdout: &str) -> Option<BlameInfo> {
-    let mut author = String::from("Unknown");
-    let mut date = String::from("Unknown");
-
-    for line in stdout.lines() {
-        if let Some(val) = line.strip_prefix("author ") {
-            author = val.trim().to_string();
-        } else if let Some(val) = line.strip_prefix("author-time ") {
-            if let Ok(ts) = val.trim().parse::<i64>() {
-                if let Some(dt) = chrono::DateTime::from_timestamp(ts, 0) {
-                    date = dt.format("%Y-%m-%d").to_string();
+/// Look up the blob oid of `file_path` as it stands in the HEAD tree. Blame
+/// results are keyed on this so that committing a change to a file
+/// invalidates its cache entry automatically — the HEAD blob oid changes
+/// whenever the committed content does.
+fn head_blob_oid(repo: &Repository, file_path: &str) -> Option<git2::Oid> {
+    let tree = repo.head().ok()?.peel_to_tree().ok()?;
+    tree.get_path(Path::new(file_path)).ok().map(|e| e.id())
+}
+
+/// Blame a file's requested lines, consulting the persistent `blame_cache`
+/// table keyed by `(file_path, blob_oid)` first. A cache hit for the exact
+/// blob oid is sliced directly with no git2 blame call at all; a miss (or a
+/// request for lines not yet in a partially-cached entry) blames only the
+/// missing lines and merges them back into the cached row. Since blob oids
+/// change whenever file content changes, a stale cache entry is structurally
+/// impossible — there's nothing to invalidate, only more to fill in.
+fn blame_file_lines_cached(
+    conn: &Connection,
+    repo: &Repository,
+    file_path: &str,
+    line_numbers: &[usize],
+) -> Result<HashMap<usize, BlameInfo>, TodoTrackError> {
+    let Some(blob_oid) = head_blob_oid(repo, file_path) else {
+        // Not in HEAD (e.g. untracked or uncommitted new file) — nothing to
+        // key a cache entry on, so just blame directly.
+        return blame_file_lines(repo, file_path, line_numbers);
+    };
+    let blob_oid = blob_oid.to_string();
+
+    let mut cached = storage::get_blame_cache(conn, file_path, &blob_oid)?.unwrap_or_default();
+
+    let missing: Vec<usize> = line_numbers
+        .iter()
+        .copied()
+        .filter(|ln| !cached.contains_key(ln))
+        .collect();
+
+    if !missing.is_empty() {
+        let fresh = blame_file_lines(repo, file_path, &missing)?;
+        cached.extend(fresh);
+        storage::put_blame_cache(conn, file_path, &blob_oid, &cached)?;
+    }
+
+    Ok(line_numbers
+        .iter()
+        .filter_map(|ln| cached.get(ln).map(|info| (*ln, info.clone())))
+        .collect())
+}
+
+/// Batch-fill `git_author`/`git_date` on each TODO, opening the repository
+/// once for the whole run and blaming each distinct file once (not per
+/// TODO), and persisting the results back to the snapshot row. Repeat runs
+/// on unchanged files are served from the `blame_cache` table instead of
+/// re-blaming. Blame failures for an individual file are logged and
+/// otherwise ignored so one unreadable file doesn't block the rest.
+pub fn populate_blame(
+    conn: &Connection,
+    repo_root: &Path,
+    todos: &mut [StoredTodo],
+) -> Result<(), TodoTrackError> {
+    let repo = Repository::discover(repo_root).map_err(|e| TodoTrackError::GitBlame {
+        file: repo_root.display().to_string(),
+        reason: e.message().to_string(),
+    })?;
+
+    let mut by_file: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, todo) in todos.iter().enumerate() {
+        by_file.entry(todo.file_path.clone()).or_default().push(idx);
+    }
+
+    for (file_path, indices) in &by_file {
+        let line_numbers: Vec<usize> = indices
+            .iter()
+            .map(|&i| todos[i].line_number as usize)
+            .collect();
+
+        match blame_file_lines_cached(conn, &repo, file_path, &line_numbers) {
+            Ok(blame_map) => {
+                for &idx in indices {
+                    let ln = todos[idx].line_number as usize;
+                    if let Some(info) = blame_map.get(&ln) {
+                        todos[idx].git_author = Some(info.author.clone());
+                        todos[idx].git_date = Some(info.date.clone());
+                        if let Err(e) =
+                            storage::update_git_blame(conn, todos[idx].id, &info.author, &info.date)
+                        {
+                            eprintln!(
+                                "Warning: failed to save blame for {}:{}: {}",
+                                file_path, ln, e
+                            );
+                        }
+                    }
                 }
             }
+            Err(e) => {
+                eprintln!("Warning: git blame failed for {}: {}", file_path, e);
+            }
         }
     }
 
-    Some(BlameInfo { author, date })
+    Ok(())
 }
 
 /// Check if the given path is inside a git repository.
@@ -159,3 +202,127 @@ pub fn is_git_repo(path: &Path) -> bool {
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
+
+/// Build the credentials callback used for `git push`. Tries, in order: an
+/// ssh-agent key for the URL's username, the `REPO_TOKEN` environment
+/// variable (the same token `forge.rs` uses for API calls) as an HTTPS
+/// username/password pair, and finally the user's configured git credential
+/// helper.
+fn push_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|url, username_from_url, allowed_types| {
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            if let Some(username) = username_from_url {
+                if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+                    return Ok(cred);
+                }
+            }
+        }
+        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            if let Ok(token) = std::env::var("REPO_TOKEN") {
+                let username = username_from_url.unwrap_or("x-access-token");
+                return Cred::userpass_plaintext(username, &token);
+            }
+        }
+        match git2::Config::open_default() {
+            Ok(config) => Cred::credential_helper(&config, url, username_from_url),
+            Err(_) => Cred::default(),
+        }
+    });
+    callbacks
+}
+
+/// Stage the snapshot database (and any other already-written paths, such as
+/// an exported report), commit with the repo's configured signature, and
+/// push to the named remote (default "origin") on the current branch. This
+/// is how teams version their TODO history alongside the code instead of
+/// leaving it as a local-only `.todo-track/db.sqlite`.
+pub fn commit_and_push(
+    repo_root: &Path,
+    paths: &[&Path],
+    remote_name: Option<&str>,
+    message: Option<String>,
+    snapshot_id: i64,
+    todo_count: i64,
+) -> Result<(), TodoTrackError> {
+    let sync_error = |reason: String| TodoTrackError::GitSync(reason);
+
+    let repo = Repository::discover(repo_root).map_err(|e| sync_error(e.message().to_string()))?;
+    let workdir = repo.workdir().unwrap_or(repo_root);
+
+    let head = repo.head().map_err(|e| sync_error(e.message().to_string()))?;
+    let parent = head
+        .peel_to_commit()
+        .map_err(|e| sync_error(e.message().to_string()))?;
+    let parent_tree = parent.tree().map_err(|e| sync_error(e.message().to_string()))?;
+
+    // Build the commit's tree from HEAD with only `paths` overlaid, using an
+    // in-memory index rather than `repo.index()` (the developer's real
+    // staging area). Anything the developer already `git add`ed stays
+    // untouched on disk and doesn't get swept into this auto-generated
+    // snapshot commit.
+    let mut index = Index::new().map_err(|e| sync_error(e.message().to_string()))?;
+    index
+        .read_tree(&parent_tree)
+        .map_err(|e| sync_error(e.message().to_string()))?;
+
+    for path in paths {
+        let relative = path.strip_prefix(workdir).unwrap_or(path);
+        let bytes = std::fs::read(path).map_err(|e| sync_error(e.to_string()))?;
+        let blob_id = repo.blob(&bytes).map_err(|e| sync_error(e.message().to_string()))?;
+        let entry = IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: 0o100_644,
+            uid: 0,
+            gid: 0,
+            file_size: bytes.len() as u32,
+            id: blob_id,
+            flags: 0,
+            flags_extended: 0,
+            path: relative.to_string_lossy().into_owned().into_bytes(),
+        };
+        index
+            .add(&entry)
+            .map_err(|e| sync_error(e.message().to_string()))?;
+    }
+
+    let tree_id = index
+        .write_tree_to(&repo)
+        .map_err(|e| sync_error(e.message().to_string()))?;
+    let tree = repo.find_tree(tree_id).map_err(|e| sync_error(e.message().to_string()))?;
+
+    let signature = repo.signature().map_err(|e| sync_error(e.message().to_string()))?;
+    let message = message.unwrap_or_else(|| {
+        format!("todo-track: snapshot #{snapshot_id}, {todo_count} TODOs")
+    });
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &[&parent],
+    )
+    .map_err(|e| sync_error(e.message().to_string()))?;
+
+    let branch = head
+        .shorthand()
+        .ok_or_else(|| sync_error("HEAD is not on a branch".to_string()))?;
+
+    let mut remote = repo
+        .find_remote(remote_name.unwrap_or(DEFAULT_REMOTE))
+        .map_err(|e| sync_error(e.message().to_string()))?;
+
+    let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(push_callbacks());
+    remote
+        .push(&[&refspec], Some(&mut push_options))
+        .map_err(|e| sync_error(e.message().to_string()))?;
+
+    Ok(())
+}
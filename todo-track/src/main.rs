@@ -1,7 +1,10 @@
 mod cli;
 mod commands;
+mod config;
 mod error;
+mod forge;
 mod git;
+mod output;
 mod parser;
 mod scanner;
 mod storage;
@@ -15,21 +18,58 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Command::Scan { path } => {
-            commands::scan::run(&path)?;
+        Command::Scan {
+            path,
+            no_ignore,
+            format,
+        } => {
+            commands::scan::run(&path, no_ignore, format)?;
         }
         Command::List {
             path,
             oldest,
             blame,
+            format,
         } => {
-            commands::list::run(&path, oldest, blame)?;
+            commands::list::run(&path, oldest, blame, format)?;
         }
         Command::Trend => {
             commands::trend::run()?;
         }
-        Command::Check { max } => {
-            commands::check::run(max)?;
+        Command::Check {
+            max,
+            max_age_days,
+            max_total_age,
+            format,
+        } => {
+            commands::check::run(max, max_age_days, max_total_age, format)?;
+        }
+        Command::Diff { path, from, to } => {
+            commands::diff::run(&path, from, to)?;
+        }
+        Command::Age {
+            path,
+            keyword,
+            author,
+            limit,
+        } => {
+            commands::age::run(&path, keyword, author, limit)?;
+        }
+        Command::Show { path, id } => {
+            commands::show::run(&path, id)?;
+        }
+        Command::Sync { path, file_issues } => {
+            commands::sync::run(&path, file_issues)?;
+        }
+        Command::Report { path, out } => {
+            commands::report::run(&path, &out)?;
+        }
+        Command::Publish {
+            path,
+            remote,
+            message,
+        } => {
+            commands::publish::run(&path, remote, message)?;
         }
     }
 
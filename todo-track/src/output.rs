@@ -0,0 +1,144 @@
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::scanner::FileTodo;
+use crate::storage::StoredTodo;
+
+/// A TODO normalized for machine-readable output, independent of whether it
+/// came from a fresh scan (`FileTodo`) or a stored snapshot (`StoredTodo`).
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportableTodo {
+    pub file_path: String,
+    pub line_number: i64,
+    pub keyword: String,
+    pub author: Option<String>,
+    pub issue_ref: Option<String>,
+    pub description: String,
+}
+
+impl From<&FileTodo> for ReportableTodo {
+    fn from(todo: &FileTodo) -> Self {
+        ReportableTodo {
+            file_path: todo.file_path.to_string_lossy().to_string(),
+            line_number: todo.item.line_number as i64,
+            keyword: todo.item.keyword.clone(),
+            author: todo.item.author.clone(),
+            issue_ref: todo.item.issue_ref.clone(),
+            description: todo.item.description.clone(),
+        }
+    }
+}
+
+impl From<&StoredTodo> for ReportableTodo {
+    fn from(todo: &StoredTodo) -> Self {
+        ReportableTodo {
+            file_path: todo.file_path.clone(),
+            line_number: todo.line_number,
+            keyword: todo.keyword.clone(),
+            author: todo.author.clone(),
+            issue_ref: todo.issue_ref.clone(),
+            description: todo.description.clone(),
+        }
+    }
+}
+
+/// Serialize todos as a compact JSON array.
+pub fn to_json(todos: &[ReportableTodo]) -> serde_json::Result<String> {
+    serde_json::to_string(todos)
+}
+
+/// Map a keyword to a stable SARIF rule id.
+fn rule_id(keyword: &str) -> String {
+    format!("todo-track/{}", keyword.to_lowercase())
+}
+
+/// SARIF 2.1.0's `level` enum — the only values a compliant consumer (e.g.
+/// GitHub code scanning) accepts.
+const VALID_SARIF_LEVELS: &[&str] = &["none", "note", "warning", "error"];
+
+/// Map a keyword to a SARIF severity level, preferring the `severity` set
+/// for it in `todo-track.toml` and falling back to the built-in
+/// FIXME/XXX-are-warnings convention for keywords left unconfigured or
+/// configured with a value outside SARIF's `level` enum.
+fn severity(config: &Config, keyword: &str) -> String {
+    let default = || {
+        match keyword {
+            "FIXME" | "XXX" => "warning",
+            _ => "note",
+        }
+        .to_string()
+    };
+
+    let Some(configured) = config
+        .keywords
+        .iter()
+        .find(|k| k.tag.eq_ignore_ascii_case(keyword))
+        .and_then(|k| k.severity.as_deref())
+    else {
+        return default();
+    };
+
+    let lowered = configured.to_lowercase();
+    if VALID_SARIF_LEVELS.contains(&lowered.as_str()) {
+        lowered
+    } else {
+        eprintln!(
+            "Warning: invalid severity \"{configured}\" for keyword {keyword} (SARIF allows none/note/warning/error), using default."
+        );
+        default()
+    }
+}
+
+/// Serialize todos as a SARIF 2.1.0 log with a single run, so each TODO
+/// becomes a `result` with a `physicalLocation` that CI systems like GitHub
+/// code scanning can render as an inline annotation on the offending line.
+///
+/// `config` supplies the rule list and per-keyword severity, so custom
+/// keywords and `severity` overrides from `todo-track.toml` are reflected
+/// in the emitted rules.
+pub fn to_sarif(todos: &[ReportableTodo], config: &Config) -> serde_json::Result<String> {
+    let results: Vec<serde_json::Value> = todos
+        .iter()
+        .map(|todo| {
+            serde_json::json!({
+                "ruleId": rule_id(&todo.keyword),
+                "level": severity(config, &todo.keyword),
+                "message": { "text": todo.description },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": todo.file_path },
+                        "region": { "startLine": todo.line_number }
+                    }
+                }]
+            })
+        })
+        .collect();
+
+    let rules: Vec<serde_json::Value> = config
+        .keyword_tags()
+        .iter()
+        .map(|kw| {
+            serde_json::json!({
+                "id": rule_id(kw),
+                "name": kw,
+                "defaultConfiguration": { "level": severity(config, kw) }
+            })
+        })
+        .collect();
+
+    let sarif = serde_json::json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "todo-track",
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif)
+}
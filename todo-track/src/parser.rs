@@ -16,7 +16,55 @@ pub struct TodoItem {
     pub description: String,
 }
 
-// Comment markers that indicate a line contains a comment
+/// Comment conventions for a language family, used to recognize block and
+/// line comments while scanning multi-line content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentSyntax {
+    /// `//` line comments, `/* */` block comments (C, Rust, JS, Java, Go, ...)
+    CLike,
+    /// `#` line comments, no block comments (Python, Shell, Ruby, TOML, ...)
+    HashStyle,
+    /// `<!-- -->` block comments only, no line comments (HTML, XML, Markdown)
+    Html,
+    /// `--` line comments, `/* */` block comments (SQL)
+    Sql,
+}
+
+impl CommentSyntax {
+    /// Pick a comment syntax from a file extension (without the leading
+    /// dot), defaulting to `CLike` for unknown extensions since `//` and
+    /// `/* */` are the most common markers in source trees.
+    pub fn for_extension(extension: &str) -> CommentSyntax {
+        match extension.to_lowercase().as_str() {
+            "py" | "rb" | "sh" | "bash" | "zsh" | "toml" | "yaml" | "yml" | "pl" | "r" => {
+                CommentSyntax::HashStyle
+            }
+            "html" | "htm" | "xml" | "svg" | "md" | "markdown" | "vue" => CommentSyntax::Html,
+            "sql" => CommentSyntax::Sql,
+            _ => CommentSyntax::CLike,
+        }
+    }
+
+    fn line_marker(self) -> Option<&'static str> {
+        match self {
+            CommentSyntax::CLike => Some("//"),
+            CommentSyntax::HashStyle => Some("#"),
+            CommentSyntax::Html => None,
+            CommentSyntax::Sql => Some("--"),
+        }
+    }
+
+    fn block_markers(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            CommentSyntax::CLike => Some(("/*", "*/")),
+            CommentSyntax::HashStyle => None,
+            CommentSyntax::Html => Some(("<!--", "-->")),
+            CommentSyntax::Sql => Some(("/*", "*/")),
+        }
+    }
+}
+
+// Comment markers used by the single-line `parse_line` thin wrapper.
 const COMMENT_MARKERS: &[&str] = &["//", "#", "/*", "<!--", "*", "--"];
 
 static TODO_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -24,8 +72,15 @@ static TODO_RE: LazyLock<Regex> = LazyLock::new(|| {
         .expect("TODO_RE pattern must be valid")
 });
 
+/// Matches `#123`-style references as well as the bare-paren `(123)` form
+/// that `forge::extract_issue_number` also accepts, so both normalize into
+/// the same `issue_ref` instead of the paren form silently falling through
+/// as "no reference". The bare-paren alternative is anchored to the end of
+/// the description (optionally followed by trailing punctuation) so an
+/// incidental parenthesized number elsewhere in the text — `bump timeout
+/// (30) seconds` — isn't mistaken for an issue reference.
 static ISSUE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"#(\d+)").expect("ISSUE_RE pattern must be valid")
+    Regex::new(r"#(\d+)|\((\d+)\)[.!?]?\s*$").expect("ISSUE_RE pattern must be valid")
 });
 
 /// Check whether a line contains a comment marker.
@@ -34,22 +89,120 @@ fn line_has_comment_marker(line: &str) -> bool {
     COMMENT_MARKERS.iter().any(|marker| trimmed.contains(marker))
 }
 
-/// Parse a single line of text and return a TodoItem if it contains a TODO-like comment.
-/// This is a pure function with no IO.
-pub fn parse_line(line: &str, line_number: usize) -> Option<TodoItem> {
-    if !line_has_comment_marker(line) {
-        return None;
+/// Find the first byte offset of `pattern` in `text` that isn't inside a
+/// quoted string literal, so markers like `//` or `#` embedded in string
+/// contents don't get mistaken for comments.
+fn find_outside_strings(text: &str, pattern: &str) -> Option<usize> {
+    let mut in_string: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        if let Some(quote) = in_string {
+            if c == '\\' {
+                chars.next();
+                continue;
+            }
+            if c == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            in_string = Some(c);
+            continue;
+        }
+
+        if text[idx..].starts_with(pattern) {
+            return Some(idx);
+        }
     }
 
-    let caps = TODO_RE.captures(line)?;
+    None
+}
+
+/// Split a line into the substrings that are actually comment text, given
+/// the current block-comment state. Updates `in_block` in place so the
+/// caller can carry it across lines.
+fn comment_segments(line: &str, syntax: CommentSyntax, in_block: &mut bool) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut cursor = 0usize;
+
+    loop {
+        if *in_block {
+            let (_open, close) = syntax
+                .block_markers()
+                .expect("in_block is only set when the syntax has block comments");
+
+            match line[cursor..].find(close) {
+                Some(rel) => {
+                    let end = cursor + rel + close.len();
+                    segments.push(line[cursor..end].to_string());
+                    cursor = end;
+                    *in_block = false;
+                }
+                None => {
+                    segments.push(line[cursor..].to_string());
+                    return segments;
+                }
+            }
+        } else {
+            let remainder = &line[cursor..];
+            let line_marker_pos = syntax
+                .line_marker()
+                .and_then(|m| find_outside_strings(remainder, m));
+            let block_open_pos = syntax
+                .block_markers()
+                .and_then(|(open, _)| find_outside_strings(remainder, open));
+
+            match (line_marker_pos, block_open_pos) {
+                (None, None) => return segments,
+                (None, Some(bo)) => {
+                    let (open, _) = syntax.block_markers().unwrap();
+                    cursor += bo + open.len();
+                    *in_block = true;
+                }
+                (Some(lm), None) => {
+                    segments.push(remainder[lm..].to_string());
+                    return segments;
+                }
+                (Some(lm), Some(bo)) => {
+                    if bo < lm {
+                        let (open, _) = syntax.block_markers().unwrap();
+                        cursor += bo + open.len();
+                        *in_block = true;
+                    } else {
+                        segments.push(remainder[lm..].to_string());
+                        return segments;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Build a TODO-matching regex for a custom set of keyword tags, with the
+/// same capture groups (keyword, author, description) as the default
+/// `TODO_RE`. Used when a project's `todo-track.toml` defines keywords
+/// beyond the TODO/FIXME/HACK/XXX built-ins.
+pub fn build_todo_regex(keywords: &[String]) -> Result<Regex, regex::Error> {
+    let escaped: Vec<String> = keywords.iter().map(|k| regex::escape(k)).collect();
+    let pattern = format!(r"(?i)\b({})\b(?:\(([^)]+)\))?:?\s*(.+)", escaped.join("|"));
+    Regex::new(&pattern)
+}
+
+/// Extract a TodoItem from a string already known to be comment text.
+fn extract_todo(re: &Regex, text: &str, line_number: usize) -> Option<TodoItem> {
+    let caps = re.captures(text)?;
 
     let keyword = caps.get(1)?.as_str().to_uppercase();
     let author = caps.get(2).map(|m| m.as_str().trim().to_string());
     let raw_description = caps.get(3)?.as_str().trim().to_string();
 
-    let issue_ref = ISSUE_RE
-        .captures(&raw_description)
-        .map(|c| format!("#{}", &c[1]));
+    let issue_ref = ISSUE_RE.captures(&raw_description).map(|c| {
+        let number = c.get(1).or_else(|| c.get(2)).expect("one group always matches");
+        format!("#{}", number.as_str())
+    });
 
     Some(TodoItem {
         line_number,
@@ -60,14 +213,46 @@ pub fn parse_line(line: &str, line_number: usize) -> Option<TodoItem> {
     })
 }
 
-/// Parse all lines in a string and return all found TodoItems.
-/// This is a pure function with no IO.
-pub fn parse_content(content: &str) -> Vec<TodoItem> {
-    content
-        .lines()
-        .enumerate()
-        .filter_map(|(idx, line)| parse_line(line, idx + 1))
-        .collect()
+/// Parse a single line of text in isolation and return a TodoItem if it
+/// contains a TODO-like comment. This is a thin wrapper used by the
+/// single-line tests below; it has no notion of block-comment state, so
+/// callers scanning real files should use `parse_content` instead.
+pub fn parse_line(line: &str, line_number: usize) -> Option<TodoItem> {
+    if !line_has_comment_marker(line) {
+        return None;
+    }
+
+    extract_todo(&TODO_RE, line, line_number)
+}
+
+/// Parse all lines in a string and return all found TodoItems, tracking
+/// block-comment state across lines so a TODO on a continuation line of a
+/// `/* ... */` or `<!-- -->` block is still found, and markers inside quoted
+/// strings are ignored. Recognizes the built-in TODO/FIXME/HACK/XXX
+/// keywords; use `parse_content_with_keywords` for a project-configured set.
+pub fn parse_content(content: &str, syntax: CommentSyntax) -> Vec<TodoItem> {
+    parse_content_with_keywords(content, syntax, &TODO_RE)
+}
+
+/// Same as `parse_content`, but matching against a caller-supplied keyword
+/// regex (see `build_todo_regex`) instead of the built-in TODO_RE.
+pub fn parse_content_with_keywords(
+    content: &str,
+    syntax: CommentSyntax,
+    keyword_re: &Regex,
+) -> Vec<TodoItem> {
+    let mut todos = Vec::new();
+    let mut in_block = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        for segment in comment_segments(line, syntax, &mut in_block) {
+            if let Some(item) = extract_todo(keyword_re, &segment, idx + 1) {
+                todos.push(item);
+            }
+        }
+    }
+
+    todos
 }
 
 #[cfg(test)]
@@ -101,6 +286,20 @@ mod tests {
         assert_eq!(item.issue_ref.as_deref(), Some("#42"));
     }
 
+    #[test]
+    fn test_fixme_with_bare_paren_issue() {
+        let line = "// FIXME: broken sorting (123)";
+        let item = parse_line(line, 11).unwrap();
+        assert_eq!(item.issue_ref.as_deref(), Some("#123"));
+    }
+
+    #[test]
+    fn test_todo_ignores_mid_sentence_parenthetical_number() {
+        let line = "// TODO: bump timeout (30) seconds";
+        let item = parse_line(line, 12).unwrap();
+        assert!(item.issue_ref.is_none());
+    }
+
     #[test]
     fn test_hack_comment() {
         let line = "/* HACK: temporary workaround */";
@@ -132,7 +331,7 @@ mod tests {
     #[test]
     fn test_parse_content() {
         let content = "fn main() {\n    // TODO: first thing\n    let x = 1;\n    // FIXME: second thing\n}\n";
-        let items = parse_content(content);
+        let items = parse_content(content, CommentSyntax::CLike);
         assert_eq!(items.len(), 2);
         assert_eq!(items[0].line_number, 2);
         assert_eq!(items[1].line_number, 4);
@@ -140,7 +339,7 @@ mod tests {
 
     #[test]
     fn test_no_false_positive_substring() {
-        // "TodoItem" should NOT match â€” "Todo" is a substring, not the word TODO
+        // "TodoItem" should NOT match — "Todo" is a substring, not the word TODO
         let line = "/// A parsed TodoItem extracted from source code.";
         assert!(parse_line(line, 1).is_none());
     }
@@ -151,4 +350,43 @@ mod tests {
         let item = parse_line(line, 1).unwrap();
         assert_eq!(item.keyword, "TODO");
     }
+
+    #[test]
+    fn test_block_comment_spanning_lines() {
+        let content = "/* TODO: another one\n   spanning lines */\nfn main() {}\n";
+        let items = parse_content(content, CommentSyntax::CLike);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].line_number, 1);
+        assert_eq!(items[0].description, "another one");
+    }
+
+    #[test]
+    fn test_block_comment_then_line_comment_on_same_line() {
+        let content = "/* plain comment */ // TODO: after the block\n";
+        let items = parse_content(content, CommentSyntax::CLike);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].description, "after the block");
+    }
+
+    #[test]
+    fn test_marker_inside_string_literal_ignored() {
+        let content = "let s = \"// TODO: not a real comment\";\n";
+        let items = parse_content(content, CommentSyntax::CLike);
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_hash_style_syntax_has_no_block_comments() {
+        let content = "x = 1  # TODO: python style\n";
+        let items = parse_content(content, CommentSyntax::HashStyle);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].keyword, "TODO");
+    }
+
+    #[test]
+    fn test_for_extension_picks_hash_style() {
+        assert_eq!(CommentSyntax::for_extension("py"), CommentSyntax::HashStyle);
+        assert_eq!(CommentSyntax::for_extension("rs"), CommentSyntax::CLike);
+        assert_eq!(CommentSyntax::for_extension("HTML"), CommentSyntax::Html);
+    }
 }
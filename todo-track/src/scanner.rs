@@ -1,13 +1,15 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
-use crate::parser::{self, TodoItem};
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use regex::Regex;
 
-/// Maximum file size to scan (1 MB). Files larger than this are skipped.
-const MAX_FILE_SIZE: u64 = 1_048_576;
+use crate::config::Config;
+use crate::parser::{self, TodoItem};
 
-/// Directories to always skip during scanning.
+/// Directories to always skip during scanning, regardless of ignore rules.
 const SKIP_DIRS: &[&str] = &[
     ".git",
     ".hg",
@@ -23,6 +25,11 @@ const SKIP_DIRS: &[&str] = &[
     "build",
 ];
 
+/// Project-level ignore file (gitignore syntax) for excludes that are
+/// specific to todo-track rather than VCS-level, e.g. generated code that
+/// is still tracked in git.
+const IGNORE_FILE: &str = ".todo-trackignore";
+
 /// A TODO found in a specific file, combining the parsed item with its file path.
 #[derive(Debug, Clone)]
 pub struct FileTodo {
@@ -36,6 +43,7 @@ pub struct ScanResult {
     pub todos: Vec<FileTodo>,
     pub files_scanned: usize,
     pub files_skipped: usize,
+    pub files_ignored: usize,
 }
 
 /// Check if a directory entry should be skipped.
@@ -43,79 +51,223 @@ fn should_skip_dir(dir_name: &str) -> bool {
     SKIP_DIRS.contains(&dir_name)
 }
 
-/// Scan a directory tree for TODO comments.
-/// Skips files > MAX_FILE_SIZE, non-UTF-8 files, and known non-source directories.
-pub fn scan_directory(root: &Path) -> ScanResult {
-    let mut todos = Vec::new();
-    let mut files_scanned: usize = 0;
-    let mut files_skipped: usize = 0;
-
-    let walker = WalkDir::new(root).follow_links(false).into_iter();
-
-    for entry in walker.filter_entry(|e| {
-        if e.file_type().is_dir() {
-            if let Some(name) = e.file_name().to_str() {
-                return !should_skip_dir(name);
-            }
+/// Outcome of reading and parsing a single walked entry.
+enum EntryOutcome {
+    Scanned(Vec<FileTodo>),
+    Skipped,
+    NotAFile,
+}
+
+/// Read and parse a single walked entry. Pure aside from the filesystem
+/// read, so it can run on any thread.
+fn scan_entry(
+    entry: Result<ignore::DirEntry, ignore::Error>,
+    root: &Path,
+    max_file_size: u64,
+    keyword_re: &Regex,
+) -> EntryOutcome {
+    let entry = match entry {
+        Ok(e) => e,
+        Err(_) => return EntryOutcome::Skipped,
+    };
+
+    if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+        return EntryOutcome::NotAFile;
+    }
+
+    let path = entry.path();
+
+    // Skip files larger than max_file_size
+    let metadata = match fs::metadata(path) {
+        Ok(m) => m,
+        Err(_) => return EntryOutcome::Skipped,
+    };
+
+    if metadata.len() > max_file_size {
+        return EntryOutcome::Skipped;
+    }
+
+    // Read the file, skipping non-UTF-8 files gracefully
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return EntryOutcome::Skipped,
+    };
+
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let syntax = parser::CommentSyntax::for_extension(extension);
+
+    // Store a path relative to the root for cleaner output
+    let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+
+    let todos = parser::parse_content_with_keywords(&content, syntax, keyword_re)
+        .into_iter()
+        .map(|item| FileTodo {
+            file_path: relative.clone(),
+            item,
+        })
+        .collect();
+
+    EntryOutcome::Scanned(todos)
+}
+
+/// Build the keyword-matching regex for a config, falling back to the
+/// built-in TODO/FIXME/HACK/XXX keywords (and a stderr warning) if the
+/// configured tags don't form a valid regex. Tags are regex-escaped before
+/// construction, so their contents are matched literally.
+fn keyword_regex(config: &Config) -> Regex {
+    match parser::build_todo_regex(&config.keyword_tags()) {
+        Ok(re) => re,
+        Err(e) => {
+            eprintln!("Warning: invalid keyword configuration ({e}), using defaults.");
+            parser::build_todo_regex(&Config::default_keywords().keyword_tags())
+                .expect("default keyword regex must be valid")
         }
-        true
-    }) {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => {
-                files_skipped += 1;
-                continue;
+    }
+}
+
+/// Count files under `root` that a walk would visit if `.gitignore`,
+/// `.git/info/exclude`, and `.todo-trackignore` were not applied — same
+/// `SKIP_DIRS` pruning and `config` overrides as `scan_directory`, just
+/// without the ignore-file filters. Used to work out how many files an
+/// ignore-respecting walk left out.
+fn count_unfiltered_files(root: &Path, config: &Config) -> usize {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .git_ignore(false)
+        .git_global(false)
+        .git_exclude(false)
+        .ignore(false)
+        .filter_entry(|e| {
+            if e.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                if let Some(name) = e.file_name().to_str() {
+                    return !should_skip_dir(name);
+                }
             }
-        };
+            true
+        });
 
-        if !entry.file_type().is_file() {
-            continue;
-        }
+    if let Ok(overrides) = build_overrides(root, config) {
+        builder.overrides(overrides);
+    }
 
-        let path = entry.path();
+    builder
+        .build()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .ok()
+                .map(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+                .unwrap_or(false)
+        })
+        .count()
+}
 
-        // Skip files larger than MAX_FILE_SIZE
-        let metadata = match fs::metadata(path) {
-            Ok(m) => m,
-            Err(_) => {
-                files_skipped += 1;
-                continue;
-            }
-        };
+/// Build an `ignore` overrides matcher from a config's include/exclude glob
+/// lists. Exclude patterns are negated (`!pattern`) per the `ignore` crate's
+/// gitignore-style override syntax; include patterns are passed through as-is.
+fn build_overrides(root: &Path, config: &Config) -> Result<ignore::overrides::Override, ignore::Error> {
+    let mut builder = OverrideBuilder::new(root);
 
-        if metadata.len() > MAX_FILE_SIZE {
-            files_skipped += 1;
-            continue;
-        }
+    for pattern in &config.scan.include {
+        builder.add(pattern)?;
+    }
+    for pattern in &config.scan.exclude {
+        builder.add(&format!("!{pattern}"))?;
+    }
 
-        // Read the file, skipping non-UTF-8 files gracefully
-        let content = match fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => {
-                files_skipped += 1;
-                continue;
+    builder.build()
+}
+
+/// Scan a directory tree for TODO comments.
+///
+/// Honors `.gitignore`, `.git/info/exclude`, and global excludes via the
+/// `ignore` crate, plus a project-level `.todo-trackignore` (same syntax),
+/// so generated code and vendored files aren't pulled into snapshots. Pass
+/// `no_ignore` to disable all of that and walk everything except the
+/// always-skipped directories in `SKIP_DIRS`.
+///
+/// `files_skipped` counts files skipped because of an IO error, the size
+/// cap, or invalid UTF-8. `files_ignored` separately counts files excluded
+/// by `.gitignore`/`.todo-trackignore` rules (always 0 when `no_ignore` is
+/// set), so the two stay distinguishable: "skipped" means broken, "ignored"
+/// means intentionally out of scope.
+///
+/// Directory pruning happens on the (single-threaded) walk itself; reading
+/// and parsing the resulting files is parallelized with rayon, since IO and
+/// regex matching dominate runtime on large trees. The merged TODOs are
+/// sorted by `(file_path, line_number)` before returning so snapshots stay
+/// stable across runs regardless of thread scheduling.
+pub fn scan_directory(root: &Path, no_ignore: bool, config: &Config) -> ScanResult {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .git_ignore(!no_ignore)
+        .git_global(!no_ignore)
+        .git_exclude(!no_ignore)
+        .ignore(!no_ignore)
+        .hidden(!no_ignore);
+    if !no_ignore {
+        // The `ignore` crate has no standalone toggle for custom ignore
+        // filenames, so this must be gated by hand to honor `no_ignore`.
+        builder.add_custom_ignore_filename(IGNORE_FILE);
+    }
+    builder
+        .filter_entry(|e| {
+            if e.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                if let Some(name) = e.file_name().to_str() {
+                    return !should_skip_dir(name);
+                }
             }
-        };
-
-        files_scanned += 1;
-
-        let items = parser::parse_content(&content);
-        for item in items {
-            // Store a path relative to the root for cleaner output
-            let relative = path
-                .strip_prefix(root)
-                .unwrap_or(path)
-                .to_path_buf();
-            todos.push(FileTodo {
-                file_path: relative,
-                item,
-            });
-        }
+            true
+        });
+
+    if let Ok(overrides) = build_overrides(root, config) {
+        builder.overrides(overrides);
     }
 
+    let entries: Vec<_> = builder.build().collect();
+    let max_file_size = config.scan.max_file_size;
+    let keyword_re = keyword_regex(config);
+
+    let (mut todos, files_scanned, files_skipped) = entries
+        .into_par_iter()
+        .fold(
+            || (Vec::new(), 0usize, 0usize),
+            |(mut todos, mut scanned, mut skipped), entry| {
+                match scan_entry(entry, root, max_file_size, &keyword_re) {
+                    EntryOutcome::Scanned(mut found) => {
+                        todos.append(&mut found);
+                        scanned += 1;
+                    }
+                    EntryOutcome::Skipped => skipped += 1,
+                    EntryOutcome::NotAFile => {}
+                }
+                (todos, scanned, skipped)
+            },
+        )
+        .reduce(
+            || (Vec::new(), 0, 0),
+            |mut a, b| {
+                a.0.extend(b.0);
+                (a.0, a.1 + b.1, a.2 + b.2)
+            },
+        );
+
+    todos.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then(a.item.line_number.cmp(&b.item.line_number))
+    });
+
+    let files_ignored = if no_ignore {
+        0
+    } else {
+        count_unfiltered_files(root, config).saturating_sub(files_scanned + files_skipped)
+    };
+
     ScanResult {
         todos,
         files_scanned,
         files_skipped,
+        files_ignored,
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -5,6 +6,7 @@ use chrono::Utc;
 use rusqlite::{params, Connection};
 
 use crate::error::TodoTrackError;
+use crate::git::BlameInfo;
 use crate::scanner::FileTodo;
 
 /// A snapshot row from the database.
@@ -60,6 +62,12 @@ pub fn open_db(root: &Path) -> Result<Connection, TodoTrackError> {
             git_author TEXT,
             git_date TEXT,
             FOREIGN KEY (snapshot_id) REFERENCES snapshots(id)
+        );
+        CREATE TABLE IF NOT EXISTS blame_cache (
+            file_path TEXT NOT NULL,
+            blob_oid TEXT NOT NULL,
+            data TEXT NOT NULL,
+            PRIMARY KEY (file_path, blob_oid)
         );",
     )?;
 
@@ -131,6 +139,20 @@ pub fn update_git_blame(
     Ok(())
 }
 
+/// Update the issue_ref for a specific todo row, e.g. after filing a new
+/// forge issue for a bare TODO.
+pub fn update_issue_ref(
+    conn: &Connection,
+    todo_id: i64,
+    issue_ref: &str,
+) -> Result<(), TodoTrackError> {
+    conn.execute(
+        "UPDATE todos SET issue_ref = ?1 WHERE id = ?2",
+        params![issue_ref, todo_id],
+    )?;
+    Ok(())
+}
+
 /// Get all snapshots ordered by timestamp (newest first).
 pub fn get_snapshots(conn: &Connection) -> Result<Vec<Snapshot>, TodoTrackError> {
     let mut stmt = conn.prepare(
@@ -172,6 +194,55 @@ pub fn get_latest_snapshot(conn: &Connection) -> Result<Option<Snapshot>, TodoTr
     }
 }
 
+/// Get a single snapshot by ID.
+pub fn get_snapshot(conn: &Connection, snapshot_id: i64) -> Result<Option<Snapshot>, TodoTrackError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, timestamp, todo_count FROM snapshots WHERE id = ?1",
+    )?;
+
+    let mut rows = stmt.query_map(params![snapshot_id], |row| {
+        Ok(Snapshot {
+            id: row.get(0)?,
+            timestamp: row.get(1)?,
+            todo_count: row.get(2)?,
+        })
+    })?;
+
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+/// Get a single todo by its row id.
+pub fn get_todo_by_id(conn: &Connection, todo_id: i64) -> Result<Option<StoredTodo>, TodoTrackError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, snapshot_id, file_path, line_number, keyword, author, issue_ref, description, git_author, git_date
+         FROM todos
+         WHERE id = ?1",
+    )?;
+
+    let mut rows = stmt.query_map(params![todo_id], |row| {
+        Ok(StoredTodo {
+            id: row.get(0)?,
+            snapshot_id: row.get(1)?,
+            file_path: row.get(2)?,
+            line_number: row.get(3)?,
+            keyword: row.get(4)?,
+            author: row.get(5)?,
+            issue_ref: row.get(6)?,
+            description: row.get(7)?,
+            git_author: row.get(8)?,
+            git_date: row.get(9)?,
+        })
+    })?;
+
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
 /// Get all todos for a given snapshot.
 pub fn get_todos_for_snapshot(
     conn: &Connection,
@@ -206,6 +277,53 @@ pub fn get_todos_for_snapshot(
     Ok(todos)
 }
 
+/// Look up a cached blame result for a specific file blob. Returns `None`
+/// when this exact `(file_path, blob_oid)` pair has never been blamed
+/// before; a hit can never be stale since the oid only changes alongside
+/// the file's content.
+pub fn get_blame_cache(
+    conn: &Connection,
+    file_path: &str,
+    blob_oid: &str,
+) -> Result<Option<HashMap<usize, BlameInfo>>, TodoTrackError> {
+    let mut stmt =
+        conn.prepare("SELECT data FROM blame_cache WHERE file_path = ?1 AND blob_oid = ?2")?;
+
+    let mut rows = stmt.query_map(params![file_path, blob_oid], |row| {
+        let data: String = row.get(0)?;
+        Ok(data)
+    })?;
+
+    match rows.next() {
+        Some(data) => {
+            let data = data?;
+            let map = serde_json::from_str(&data).unwrap_or_default();
+            Ok(Some(map))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Insert or replace the cached blame result for a specific file blob.
+pub fn put_blame_cache(
+    conn: &Connection,
+    file_path: &str,
+    blob_oid: &str,
+    blame: &HashMap<usize, BlameInfo>,
+) -> Result<(), TodoTrackError> {
+    let data = serde_json::to_string(blame).map_err(|e| TodoTrackError::GitBlame {
+        file: file_path.to_string(),
+        reason: format!("failed to serialize blame cache: {e}"),
+    })?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO blame_cache (file_path, blob_oid, data) VALUES (?1, ?2, ?3)",
+        params![file_path, blob_oid, data],
+    )?;
+
+    Ok(())
+}
+
 /// Get the latest TODO count from the most recent snapshot.
 pub fn get_latest_todo_count(conn: &Connection) -> Result<Option<i64>, TodoTrackError> {
     match get_latest_snapshot(conn)? {